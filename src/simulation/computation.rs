@@ -2,15 +2,67 @@ use bevy::prelude::*;
 
 use super::{schedule::RunSimulation, types::*};
 
+/// Per-fluid thermodynamic constants driving [`generate_steam`]. Swapping this
+/// resource lets a reactor model a different coolant (e.g. heavy water) without
+/// touching the boiling logic itself.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct FluidProperties {
+    /// Specific heat capacity, in J/g/K.
+    pub specific_heat_capacity: f32,
+    /// Latent heat of vaporization, in J/g.
+    pub latent_heat_of_vaporization: f32,
+    /// Antoine equation coefficients (`log10(P_mmHg) = a - b / (c + t_celsius)`).
+    pub antoine_a: f32,
+    pub antoine_b: f32,
+    pub antoine_c: f32,
+    /// Boiling point to fall back to once Antoine's equation stops being
+    /// physically meaningful (super-critical pressure), in degrees Celsius.
+    pub max_boiling_point: f32,
+}
+
+impl Default for FluidProperties {
+    fn default() -> Self {
+        // Light water at atmospheric pressure.
+        Self {
+            specific_heat_capacity: 4.1816,
+            latent_heat_of_vaporization: 2257.0,
+            antoine_a: 8.07131,
+            antoine_b: 1730.63,
+            antoine_c: 233.426,
+            max_boiling_point: 374.0, // approaching water's critical point
+        }
+    }
+}
+
+impl FluidProperties {
+    /// Boiling point in degrees Celsius for the given pressure in atmospheres,
+    /// via the Antoine vapor-pressure relation.
+    fn boiling_point_celsius(&self, pressure_atm: f32) -> f32 {
+        let pressure_mmhg = pressure_atm * 760.0;
+        let denominator = self.antoine_a - pressure_mmhg.max(f32::MIN_POSITIVE).log10();
+        if denominator <= 0.0 {
+            return self.max_boiling_point;
+        }
+        (self.antoine_b / denominator - self.antoine_c).min(self.max_boiling_point)
+    }
+}
+
 pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<FluidProperties>();
+    app.init_resource::<WorstCaseSafety>();
+    app.add_event::<ApproachingDryout>();
+    secondary_loop::plugin(app);
     app.add_systems(
         RunSimulation,
         (
+            burn_fuel,
+            update_xenon_poisoning,
             update_local_reactivity,
             update_edge_reactivity,
             update_total_reactivity,
             update_temperature,
             generate_steam,
+            update_safety_status,
         )
             .chain(),
     );
@@ -20,6 +72,144 @@ pub(super) fn plugin(app: &mut App) {
     );
 }
 
+/// Per-cell thermal-hydraulic safety margin: void fraction and Critical Power Ratio
+/// against the local dryout/burnout limit.
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct SafetyStatus {
+    pub void_fraction: f32,
+    pub critical_power_ratio: f32,
+    pub approaching_dryout: bool,
+}
+
+/// Worst-case margin across the whole core this tick, for alarms/dashboards that
+/// don't want to scan every cell themselves.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct WorstCaseSafety {
+    pub min_critical_power_ratio: f32,
+    pub max_void_fraction: f32,
+}
+
+impl Default for WorstCaseSafety {
+    fn default() -> Self {
+        Self {
+            min_critical_power_ratio: f32::MAX,
+            max_void_fraction: 0.0,
+        }
+    }
+}
+
+/// Fired when a cell's Critical Power Ratio drops below
+/// `SimulationConfig::critical_power_ratio_limit`, or its void fraction exceeds
+/// `SimulationConfig::void_fraction_dryout_limit`.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ApproachingDryout {
+    pub cell: Entity,
+    pub critical_power_ratio: f32,
+    pub void_fraction: f32,
+}
+
+fn update_safety_status(
+    config: Res<SimulationConfig>,
+    mut worst_case: ResMut<WorstCaseSafety>,
+    mut dryout_events: EventWriter<ApproachingDryout>,
+    mut query: Query<
+        (
+            Entity,
+            &mut SafetyStatus,
+            &SteamLevel,
+            &CoolantLevel,
+            &CoolantFlow,
+            &Reactivity,
+        ),
+        With<ReactorCell>,
+    >,
+) {
+    *worst_case = WorstCaseSafety::default();
+
+    for (cell, mut status, steam_level, coolant_level, coolant_flow, reactivity) in &mut query {
+        let void_fraction =
+            steam_level.0 / (steam_level.0 + coolant_level.0).max(f32::MIN_POSITIVE);
+
+        let critical_power = (config.critical_power_base
+            - config.critical_power_void_penalty * void_fraction
+            - config.critical_power_flow_penalty * (1.0 - coolant_flow.0))
+            .max(0.0);
+        let actual_power = reactivity.0 * config.heat_generation_factor;
+        let critical_power_ratio = if actual_power > 0.0 {
+            critical_power / actual_power
+        } else {
+            f32::MAX
+        };
+
+        let approaching_dryout = critical_power_ratio < config.critical_power_ratio_limit
+            || void_fraction > config.void_fraction_dryout_limit;
+
+        status.void_fraction = void_fraction;
+        status.critical_power_ratio = critical_power_ratio;
+        status.approaching_dryout = approaching_dryout;
+
+        worst_case.min_critical_power_ratio = worst_case
+            .min_critical_power_ratio
+            .min(critical_power_ratio);
+        worst_case.max_void_fraction = worst_case.max_void_fraction.max(void_fraction);
+
+        if approaching_dryout {
+            dryout_events.write(ApproachingDryout {
+                cell,
+                critical_power_ratio,
+                void_fraction,
+            });
+        }
+    }
+}
+
+/// Remaining fissile fraction of a cell's fuel load, from `1.0` (fresh) down to `0.0` (spent).
+#[derive(Component, Debug, Clone, Copy)]
+pub struct FuelInventory(pub f32);
+
+impl Default for FuelInventory {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+/// I-135/Xe-135 concentrations for the standard two-pool xenon poisoning model.
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct XenonPoison {
+    pub iodine: f32,
+    pub xenon: f32,
+}
+
+// Rates below are tuned per simulation tick, matching the rest of this module
+// (e.g. `update_temperature`), rather than scaled by an explicit `dt`.
+fn burn_fuel(
+    config: Res<SimulationConfig>,
+    mut query: Query<(&mut FuelInventory, &Reactivity), With<ReactorCell>>,
+) {
+    for (mut fuel, reactivity) in &mut query {
+        // Negative reactivity (e.g. from a heavy xenon penalty) isn't un-burning fuel.
+        let flux = reactivity.0.max(0.0);
+        fuel.0 = (fuel.0 - config.fuel_burn_rate * flux).max(0.0);
+    }
+}
+
+fn update_xenon_poisoning(
+    config: Res<SimulationConfig>,
+    mut query: Query<(&mut XenonPoison, &Reactivity), With<ReactorCell>>,
+) {
+    for (mut poison, reactivity) in &mut query {
+        let flux = reactivity.0.max(0.0);
+
+        let d_iodine = config.iodine_yield * flux - config.iodine_decay_constant * poison.iodine;
+        let d_xenon = config.xenon_yield * flux + config.iodine_decay_constant * poison.iodine
+            - config.xenon_decay_constant * poison.xenon
+            - config.xenon_absorption_cross_section * flux * poison.xenon;
+
+        poison.iodine = (poison.iodine + d_iodine).max(0.0);
+        poison.xenon = (poison.xenon + d_xenon).max(0.0);
+    }
+}
+
 fn update_edge_reactivity(
     cores: Query<&ReactorCore>,
     mut edges: Query<(&mut Reactivity, &ReactorEdge, &ChildOf), Without<ReactorCell>>,
@@ -43,12 +233,31 @@ fn update_edge_reactivity(
 
 fn update_local_reactivity(
     config: Res<SimulationConfig>,
-    mut query: Query<(&mut LocalReactivity, &ControlRod, &CoolantLevel), With<ReactorCell>>,
+    mut query: Query<
+        (
+            &mut LocalReactivity,
+            &ControlRod,
+            &CoolantLevel,
+            &SuspendedSteam,
+            &FuelInventory,
+            &XenonPoison,
+        ),
+        With<ReactorCell>,
+    >,
 ) {
-    for (mut local_reactivity, control_rod, coolant_level) in &mut query {
+    for (mut local_reactivity, control_rod, coolant_level, suspended_steam, fuel, xenon) in
+        &mut query
+    {
         let rod_factor = 1.0 - control_rod.0; // control rods absorb
-        let coolant_factor = 1.0 + config.void_reactivity_boost * (1.0 - coolant_level.0); // steam = more reactivity
-        local_reactivity.0 = config.base_reactivity * rod_factor * coolant_factor;
+                                              // Steam = more reactivity; suspended bubbles that haven't separated out yet
+                                              // give an extra transient boost on top of the steady-state void feedback.
+        let coolant_factor = 1.0
+            + config.void_reactivity_boost * (1.0 - coolant_level.0)
+            + config.suspended_steam_reactivity_boost * suspended_steam.0;
+        let xenon_penalty = config.xenon_reactivity_penalty_factor * xenon.xenon;
+
+        local_reactivity.0 =
+            config.base_reactivity * fuel.0 * rod_factor * coolant_factor - xenon_penalty;
     }
 }
 
@@ -141,15 +350,28 @@ fn update_temperature(
     Ok(())
 }
 
+/// Boiled-off coolant held as suspended bubbles, not yet separated into the free
+/// (pressure-producing) steam pool. See [`generate_steam`].
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct SuspendedSteam(pub f32);
+
+/// Condensed coolant the secondary loop is feeding back into this cell, added on top
+/// of its own `CoolantFlow` on the next run of [`generate_steam`]. See [`secondary_loop`].
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct CondensateReturn(pub f32);
+
 fn generate_steam(
     config: Res<SimulationConfig>,
+    fluid: Res<FluidProperties>,
     mut query: Query<
         (
             &mut SteamOutput,
             &mut CoolantLevel,
             &mut SteamLevel,
+            &mut SuspendedSteam,
             &Temperature,
             &CoolantFlow,
+            &CondensateReturn,
             &mut Pressure,
             &SteamPullCapacity,
         ),
@@ -160,28 +382,52 @@ fn generate_steam(
         mut steam_output,
         mut coolant_level,
         mut steam_level,
+        mut suspended_steam,
         temperature,
         coolant_flow,
+        condensate_return,
         mut pressure,
         steam_pull_capacity,
     ) in &mut query
     {
-        // Boiling point of water depends on pressure (roughly 3 degrees per atmosphere)
-        let boiling_point = 100.0 + (pressure.0 - 1.0) * 3.0;
+        // Boiling point of water depends on pressure, per the Antoine vapor-pressure relation.
+        let boiling_point = fluid.boiling_point_celsius(pressure.0);
 
-        // Convert water currently in the cell into steam
-        // The higher the temperature, the more water vaporizes
-        if temperature.0 > boiling_point && coolant_level.0 > 0.0 {
+        // Convert water currently in the cell into steam, treating boiling as a real
+        // heat sink: the energy it takes to vaporize the coolant is removed from
+        // what's left, instead of the temperature and steam level drifting independently.
+        if temperature.0 > boiling_point
+            && coolant_level.0 > 0.0
+            && config.coolant_mass_per_cell > 0.0
+        {
             let heat_excess = temperature.0 - boiling_point;
-            let available_energy = heat_excess * config.energy_per_heat_unit; // total "extra" thermal energy
+            let mass_in_cell = coolant_level.0 * config.coolant_mass_per_cell;
+            let available_energy = heat_excess * mass_in_cell * fluid.specific_heat_capacity;
+
+            let boiled_mass =
+                (available_energy / fluid.latent_heat_of_vaporization).min(mass_in_cell);
+            let remaining_mass = mass_in_cell - boiled_mass;
 
-            let max_steam_from_energy = available_energy / config.energy_required_per_unit;
-            let coolant_boiled = max_steam_from_energy.min(coolant_level.0);
+            if remaining_mass > 0.0 {
+                let energy_removed = boiled_mass * fluid.latent_heat_of_vaporization;
+                temperature.0 -= energy_removed / (remaining_mass * fluid.specific_heat_capacity);
+            }
 
-            coolant_level.0 -= coolant_boiled;
-            steam_level.0 += coolant_boiled;
+            let boiled_fraction = boiled_mass / config.coolant_mass_per_cell;
+            coolant_level.0 -= boiled_fraction;
+            // Newly boiled coolant enters suspension as bubbles rather than
+            // immediately joining the free (pressure-producing) steam pool.
+            suspended_steam.0 += boiled_fraction;
         }
 
+        // Suspended bubbles rise and separate out over a finite time: the half-life
+        // grows with coolant depth and the configured inverse bubble-rise speed.
+        let bubble_half_life = coolant_level.0.max(0.01) * config.bubble_rise_time_constant;
+        let retained = 0.5f32.powf(1.0 / bubble_half_life);
+        let separated = suspended_steam.0 * (1.0 - retained);
+        suspended_steam.0 -= separated;
+        steam_level.0 += separated;
+
         let available_space = (1.0 - coolant_level.0).max(0.01); // prevent div by zero
         let gas_amount = steam_level.0 * config.steam_expansion_ratio;
         let temp_kelvin = (temperature.0 + 273.15).max(0.0);
@@ -203,15 +449,115 @@ fn generate_steam(
         steam_level.0 -= steam_output.0;
 
         let space_remaining = 1.0 - (coolant_level.0 + steam_level.0);
-        let added_coolant = coolant_flow.0.min(space_remaining);
+        let added_coolant = (coolant_flow.0 + condensate_return.0).min(space_remaining);
         coolant_level.0 += added_coolant;
     }
 }
 
+/// The secondary loop: turbine power generation and condenser return, closing the
+/// steam/coolant circuit so the condenser's state feeds back into the core through
+/// the condensate return, instead of `SteamOutput` simply vanishing.
+mod secondary_loop {
+    use bevy::prelude::*;
+
+    use super::super::{schedule::RunSimulation, types::*};
+    use super::{CondensateReturn, FluidProperties};
+
+    /// Combined steam mass arriving at the turbine from every cell's `SteamOutput` this tick.
+    #[derive(Resource, Default, Debug, Clone, Copy)]
+    pub struct TurbineInlet(pub f32);
+
+    /// Electrical power produced by the turbine this tick.
+    #[derive(Resource, Default, Debug, Clone, Copy)]
+    pub struct PowerOutput(pub f32);
+
+    /// A fluid holder of finite thermal mass that condenses spent turbine steam back
+    /// into liquid coolant and returns it to the cells as `CondensateReturn`, on top
+    /// of their own `CoolantFlow`.
+    #[derive(Resource, Debug, Clone, Copy, Default)]
+    pub struct Condenser {
+        /// Spent steam mass waiting to be condensed.
+        pub steam_mass: f32,
+        /// Condensed liquid mass currently held, awaiting return to the cells.
+        pub liquid_mass: f32,
+        /// Heat accumulated in the condenser from condensing steam.
+        pub heat: f32,
+    }
+
+    pub(super) fn plugin(app: &mut App) {
+        app.init_resource::<TurbineInlet>();
+        app.init_resource::<PowerOutput>();
+        app.init_resource::<Condenser>();
+        app.add_systems(
+            RunSimulation,
+            (collect_turbine_inlet, generate_power, condense_and_return)
+                .chain()
+                .after(super::generate_steam),
+        );
+    }
+
+    fn collect_turbine_inlet(
+        config: Res<SimulationConfig>,
+        mut inlet: ResMut<TurbineInlet>,
+        cells: Query<&SteamOutput, With<ReactorCell>>,
+    ) {
+        // `SteamOutput` is a fraction of a cell's volume; convert it back to mass.
+        let steam_fraction: f32 = cells.iter().map(|output| output.0).sum();
+        inlet.0 = steam_fraction * config.coolant_mass_per_cell;
+    }
+
+    fn generate_power(
+        config: Res<SimulationConfig>,
+        fluid: Res<FluidProperties>,
+        inlet: Res<TurbineInlet>,
+        mut power: ResMut<PowerOutput>,
+    ) {
+        power.0 = inlet.0 * fluid.latent_heat_of_vaporization * config.turbine_efficiency;
+    }
+
+    fn condense_and_return(
+        config: Res<SimulationConfig>,
+        fluid: Res<FluidProperties>,
+        inlet: Res<TurbineInlet>,
+        mut condenser: ResMut<Condenser>,
+        mut cells: Query<&mut CondensateReturn, With<ReactorCell>>,
+    ) {
+        // Passive heat rejection to the environment (e.g. cooling towers).
+        condenser.heat = (condenser.heat - config.condenser_cooling_rate).max(0.0);
+
+        // The condenser's ability to condense more steam falls as it saturates with heat,
+        // so under-cooling it backs pressure up into the core by starving the condensate return.
+        let saturation = (condenser.heat / config.condenser_heat_capacity.max(f32::MIN_POSITIVE))
+            .clamp(0.0, 1.0);
+        let condense_rate = config.condenser_condense_rate * (1.0 - saturation);
+
+        // Steam the condenser can't take this tick waits in it for the next one.
+        let pending_steam = condenser.steam_mass + inlet.0;
+        let condensed_mass = pending_steam.min(condense_rate);
+        condenser.steam_mass = pending_steam - condensed_mass;
+        condenser.liquid_mass += condensed_mass;
+        condenser.heat += condensed_mass * fluid.latent_heat_of_vaporization;
+
+        let returned_mass = condenser.liquid_mass.min(config.condenser_return_rate);
+        condenser.liquid_mass -= returned_mass;
+
+        let cell_count = cells.iter().len().max(1) as f32;
+        let returned_flow_per_cell =
+            returned_mass / cell_count / config.coolant_mass_per_cell.max(f32::MIN_POSITIVE);
+
+        // Added on top of each cell's own CoolantFlow in `generate_steam`, not
+        // overwriting it, so the operator-configured feed is never clobbered.
+        for mut condensate_return in &mut cells {
+            condensate_return.0 = returned_flow_per_cell;
+        }
+    }
+}
+
 #[test]
 fn test_generates_steam() {
     let mut app = App::new();
     app.init_resource::<SimulationConfig>();
+    app.init_resource::<FluidProperties>();
 
     app.add_systems(Update, generate_steam);
 
@@ -222,14 +568,20 @@ fn test_generates_steam() {
             SteamOutput::default(),
             CoolantLevel::default(),
             SteamLevel::default(),
+            SuspendedSteam::default(),
             Temperature::default(),
             CoolantFlow::default(),
+            CondensateReturn::default(),
             Pressure::default(),
             SteamPullCapacity::default(),
         ))
         .id();
 
-    app.update();
+    // Boiled coolant now lags behind as suspended bubbles before it separates into
+    // free steam, so give it enough ticks to clear rather than relying on the first one.
+    for _ in 0..64 {
+        app.update();
+    }
 
     let steam_output = app.world().get::<SteamOutput>(entity).unwrap();
 